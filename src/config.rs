@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+/// Used when neither `--model` nor the config file specify a model.
+pub const DEFAULT_MODEL: &str = "anthropic/claude-haiku-4.5";
+/// Used when neither `--base-url`, the config file, nor `OPENROUTER_BASE_URL`
+/// specify a base URL.
+pub const DEFAULT_BASE_URL: &str = "https://openrouter.ai/api/v1";
+/// Used when neither `--max-tokens` nor the config file specify a limit.
+pub const DEFAULT_MAX_TOKENS: u32 = 128;
+
+/// A named persona: a system prompt paired with the subset of tools it's
+/// allowed to call. An empty `tools` list means all tools are allowed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Role {
+    pub system_prompt: String,
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+/// TOML config, loaded from `--config` or the standard config directory.
+/// Every field is optional so a partial (or missing) file still works;
+/// unset fields fall back to the built-in defaults in this module.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    pub model: Option<String>,
+    pub base_url: Option<String>,
+    pub max_tokens: Option<u32>,
+    pub proxy: Option<String>,
+    #[serde(default)]
+    pub roles: HashMap<String, Role>,
+}
+
+impl Config {
+    /// Loads the config file at `path`, or from the standard config
+    /// directory if `path` is `None`. If `path` is `None` and no file
+    /// exists at the standard location, returns the built-in defaults (no
+    /// error) — but an explicitly-given `path` that doesn't exist is an
+    /// error, since silently falling back to defaults there would mask a
+    /// typo'd `--config` flag.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (resolved, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_config_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        if !resolved.exists() {
+            if explicit {
+                bail!("Config file not found at {}", resolved.display());
+            }
+            return Ok(Config::default());
+        }
+
+        let contents = std::fs::read_to_string(&resolved)
+            .with_context(|| format!("Failed to read config file at {}", resolved.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file at {}", resolved.display()))
+    }
+
+    pub fn role(&self, name: &str) -> Option<&Role> {
+        self.roles.get(name)
+    }
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "codecrafters-claude-code-rust")
+        .map(|dirs| dirs.config_dir().join("config.toml"))
+}
@@ -1,40 +1,150 @@
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
 use async_openai::types::chat::{
-    ChatCompletionMessageToolCall, ChatCompletionMessageToolCalls,
+    ChatCompletionMessageFunctionCall, ChatCompletionMessageToolCall,
+    ChatCompletionMessageToolCallChunk, ChatCompletionMessageToolCalls,
     ChatCompletionRequestAssistantMessageArgs, ChatCompletionRequestMessage,
-    ChatCompletionRequestToolMessage, ChatCompletionRequestUserMessage, ChatCompletionTool,
-    ChatCompletionTools, CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
-    FunctionObjectArgs,
+    ChatCompletionRequestMessageContentPartImageArgs, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestToolMessage, ChatCompletionRequestToolMessageContent,
+    ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageArgs,
+    ChatCompletionRequestUserMessageContentPart, ChatCompletionTool, ChatCompletionTools,
+    CreateChatCompletionRequest, CreateChatCompletionRequestArgs, FunctionObjectArgs, ImageUrlArgs,
 };
 use async_openai::{Client, config::OpenAIConfig};
-use clap::Parser;
+use base64::Engine;
+use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
 use serde_json::{Value, json};
+use std::collections::BTreeMap;
 use std::io::Write;
-use std::path::Path;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::{env, process};
 
+mod config;
+mod server;
+
+use config::Config;
+
+/// The content a tool call resolves to, destined for the follow-up
+/// `ChatCompletionRequestToolMessage`. Most tools just produce JSON text,
+/// but `Read` can hand back an image for vision-capable models.
+enum ToolContent {
+    Json(Value),
+    Image { mime_type: String, data: String },
+}
+
 #[derive(Parser)]
 #[command(author, version, about)]
 struct Args {
+    /// Prompt to run once and exit. If omitted, starts an interactive REPL.
     #[arg(short = 'p', long)]
-    prompt: String,
+    prompt: Option<String>,
+
+    /// Skip the interactive confirmation prompt before running side-effecting
+    /// tool calls (`Write`, `Bash`). Intended for non-interactive runs.
+    #[arg(long, visible_alias = "auto-approve")]
+    yes: bool,
+
+    /// Path to a TOML config file. Defaults to the standard config
+    /// directory if not given.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Name of a role from the config file. Prepends the role's system
+    /// prompt to the conversation and restricts the tools offered to it.
+    #[arg(long)]
+    role: Option<String>,
+
+    /// Overrides the config file's `model`.
+    #[arg(long)]
+    model: Option<String>,
+
+    /// Overrides the config file's `base_url`.
+    #[arg(long = "base-url")]
+    base_url: Option<String>,
+
+    /// Overrides the config file's `max_tokens`.
+    #[arg(long = "max-tokens")]
+    max_tokens: Option<u32>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start an HTTP server exposing an OpenAI-compatible
+    /// `/v1/chat/completions` endpoint backed by the Read/Write/Bash tools.
+    Serve {
+        /// Port to listen on.
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+}
+
+/// Whether a tool can be dispatched without asking the user first, or
+/// whether it has side effects and needs approval gating.
+#[derive(PartialEq, Eq)]
+enum ToolKind {
+    ReadOnly,
+    Execute,
+}
+
+fn tool_kind(name: &str) -> ToolKind {
+    match name {
+        "Write" | "Bash" => ToolKind::Execute,
+        _ => ToolKind::ReadOnly,
+    }
+}
+
+fn tool_name(tool: &ChatCompletionTools) -> Option<&str> {
+    if let ChatCompletionTools::Function(function) = tool {
+        Some(function.function.name.as_str())
+    } else {
+        None
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
 
-    let base_url = env::var("OPENROUTER_BASE_URL")
-        .unwrap_or_else(|_| "https://openrouter.ai/api/v1".to_string());
+    let base_url = args
+        .base_url
+        .clone()
+        .or_else(|| config.base_url.clone())
+        .or_else(|| env::var("OPENROUTER_BASE_URL").ok())
+        .unwrap_or_else(|| config::DEFAULT_BASE_URL.to_string());
 
     let api_key = env::var("OPENROUTER_API_KEY")?;
 
-    let config = OpenAIConfig::new()
+    let openai_config = OpenAIConfig::new()
         .with_api_base(base_url)
         .with_api_key(api_key);
 
-    let client = Client::with_config(config);
-    let model = "anthropic/claude-haiku-4.5";
+    let mut http_client_builder = reqwest::Client::builder();
+    if let Some(proxy_url) = &config.proxy {
+        http_client_builder = http_client_builder
+            .proxy(reqwest::Proxy::all(proxy_url).with_context(|| {
+                format!("Invalid proxy URL '{proxy_url}' in config")
+            })?);
+    }
+    let http_client = http_client_builder
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    let client = Client::with_config(openai_config).with_http_client(http_client);
+    let model = args
+        .model
+        .clone()
+        .or_else(|| config.model.clone())
+        .unwrap_or_else(|| config::DEFAULT_MODEL.to_string());
+    let max_tokens = args
+        .max_tokens
+        .or(config.max_tokens)
+        .unwrap_or(config::DEFAULT_MAX_TOKENS);
 
     let read_tool_parameters = json!(include_str!("tool_definitions/read_tool_params.json"));
     let read_tool = tool_definition_factory(
@@ -56,58 +166,337 @@ async fn main() -> Result<()> {
         "Execute a shell command. Takes a `command` argument.",
         bash_tool_parameters,
     )?;
-    let tools = vec![
+    let mut tools = vec![
         ChatCompletionTools::Function(read_tool),
         ChatCompletionTools::Function(write_tool),
         ChatCompletionTools::Function(bash_tool),
     ];
-    let user_prompt = args.prompt;
-    let mut request = CreateChatCompletionRequestArgs::default()
-        .max_completion_tokens(128_u32)
-        .model(model)
-        .messages(ChatCompletionRequestUserMessage::from(user_prompt.clone()))
-        .tools(tools)
-        .build()?;
 
-    loop {
-        let response = client.chat().create(request.clone()).await?;
-        let response_message = response.choices.first().context("No choices")?;
-
-        if let Some(ref tool_calls) = response_message.message.tool_calls {
-            let mut function_responses = Vec::new();
-            for tool_call_enum in tool_calls {
-                // Extract the function tool call from the enum.
-                if let ChatCompletionMessageToolCalls::Function(tool_call) = tool_call_enum {
-                    let name = tool_call.function.name.as_str();
-                    eprintln!("Calling {name} function.");
-                    let args = tool_call.function.arguments.as_str();
-                    let args: Value = serde_json::from_str(&args)?;
-                    eprintln!("{args:?}");
-                    match name {
-                        "Read" => call_read_tool(&tool_call, &args, &mut function_responses)?,
-                        "Write" => call_write_tool(&tool_call, &args, &mut function_responses)?,
-                        "Bash" => call_bash_tool(&tool_call, &args, &mut function_responses)?,
-                        _ => {
-                            let err_msg = format!("Unknown tool: {name}");
-                            function_responses.push((&tool_call, json!(err_msg)));
-                        }
-                    }
-                }
+    let mut messages: Vec<ChatCompletionRequestMessage> = Vec::new();
+    if let Some(role_name) = &args.role {
+        let role = config
+            .role(role_name)
+            .with_context(|| format!("Unknown role '{role_name}' in config"))?;
+        let system_message: ChatCompletionRequestMessage =
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content(role.system_prompt.clone())
+                .build()?
+                .into();
+        messages.push(system_message);
+        if !role.tools.is_empty() {
+            tools.retain(|tool| {
+                tool_name(tool).is_some_and(|name| role.tools.iter().any(|allowed| allowed == name))
+            });
+        }
+    }
+
+    match args.command {
+        Some(Command::Serve { port }) => {
+            let addr = SocketAddr::from(([127, 0, 0, 1], port));
+            server::serve(addr, client, model, max_tokens, tools, messages, args.yes).await?;
+        }
+        None => {
+            let mut request = CreateChatCompletionRequestArgs::default()
+                .max_completion_tokens(max_tokens)
+                .model(model)
+                .messages(messages)
+                .tools(tools)
+                .build()?;
+
+            if let Some(prompt) = args.prompt {
+                request
+                    .messages
+                    .push(ChatCompletionRequestUserMessage::from(prompt).into());
+                agent_turn(&client, &mut request, args.yes, |_delta| {}).await?;
+            } else {
+                run_repl(&client, request, args.yes).await?;
             }
-            append_tool_responses(&mut request, &function_responses)?;
-        } else if let Some(message) = &response_message.message.content {
-            println!("{message}");
-            break;
+        }
+    }
+    Ok(())
+}
+
+/// Drives the existing tool-calling loop for a single user turn: streams
+/// the assistant's reply, dispatches any tool calls and feeds their
+/// responses back, repeating until the assistant answers with plain text.
+/// `on_delta` is invoked with each assistant text fragment as it streams
+/// in, so the `serve` subcommand can relay real incremental output to an
+/// HTTP client instead of buffering the whole reply. Returns the final
+/// text so both the CLI/REPL and `serve` can share this loop.
+async fn agent_turn(
+    client: &Client<OpenAIConfig>,
+    request: &mut CreateChatCompletionRequest,
+    auto_approve: bool,
+    mut on_delta: impl FnMut(&str),
+) -> Result<String> {
+    loop {
+        let (content, tool_calls) = stream_chat_turn(client, request.clone(), &mut on_delta).await?;
+
+        if !tool_calls.is_empty() {
+            let ordered = run_tool_calls(tool_calls, auto_approve).await?;
+            let function_responses: Vec<(&ChatCompletionMessageToolCall, &ToolContent)> = ordered
+                .iter()
+                .map(|(tool_call, response)| (tool_call, response))
+                .collect();
+            append_tool_responses(request, &function_responses)?;
+        } else if let Some(content) = content {
+            println!();
+            return Ok(content);
         } else {
             bail!("Response had neither tool calls nor content");
         }
     }
+}
+
+/// Interactive REPL entered when no `--prompt` is given. Keeps `request`'s
+/// growing message history across turns so multi-step tasks can span
+/// several prompts, and supports a few meta-commands prefixed with `:`.
+async fn run_repl(
+    client: &Client<OpenAIConfig>,
+    mut request: CreateChatCompletionRequest,
+    auto_approve: bool,
+) -> Result<()> {
+    let base_messages = request.messages.clone();
+    println!("Interactive mode. Commands: :reset, :history, :quit.");
+
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim();
+
+        match line {
+            "" => continue,
+            ":quit" => break,
+            ":reset" => {
+                request.messages = base_messages.clone();
+                println!("History cleared.");
+                continue;
+            }
+            ":history" => {
+                for message in &request.messages {
+                    println!("{message:?}");
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        request
+            .messages
+            .push(ChatCompletionRequestUserMessage::from(line.to_string()).into());
+        agent_turn(client, &mut request, auto_approve, |_delta| {}).await?;
+    }
     Ok(())
 }
 
+/// Accumulates the fragments of a single streamed tool call, keyed by the
+/// `index` the API assigns it. Every call's accumulator is kept until the
+/// stream ends, and all of them are finalized together at that point.
+#[derive(Default)]
+struct ToolCallAccumulator {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+impl ToolCallAccumulator {
+    fn finalize(self) -> Option<ChatCompletionMessageToolCall> {
+        Some(ChatCompletionMessageToolCall {
+            id: self.id?,
+            function: ChatCompletionMessageFunctionCall {
+                name: self.name,
+                arguments: self.arguments,
+            },
+        })
+    }
+}
+
+/// Drives one turn of `create_stream`, printing assistant text deltas as
+/// they arrive, forwarding each delta to `on_delta` (so callers like the
+/// `serve` subcommand can relay them onward), and reassembling any
+/// tool-call deltas into complete `ChatCompletionMessageToolCall`s once the
+/// stream closes.
+async fn stream_chat_turn(
+    client: &Client<OpenAIConfig>,
+    request: CreateChatCompletionRequest,
+    mut on_delta: impl FnMut(&str),
+) -> Result<(Option<String>, Vec<ChatCompletionMessageToolCall>)> {
+    let mut stream = client.chat().create_stream(request).await?;
+    let mut content = String::new();
+    let mut accumulators: BTreeMap<u32, ToolCallAccumulator> = BTreeMap::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        let Some(choice) = chunk.choices.first() else {
+            continue;
+        };
+
+        if let Some(text) = &choice.delta.content {
+            print!("{text}");
+            std::io::stdout().flush()?;
+            on_delta(text);
+            content.push_str(text);
+        }
+
+        if let Some(tool_call_chunks) = &choice.delta.tool_calls {
+            for tool_call_chunk in tool_call_chunks {
+                accumulate_tool_call_chunk(&mut accumulators, tool_call_chunk);
+            }
+        }
+    }
+
+    let tool_calls = accumulators
+        .into_values()
+        .filter_map(ToolCallAccumulator::finalize)
+        .collect();
+
+    Ok((if content.is_empty() { None } else { Some(content) }, tool_calls))
+}
+
+fn accumulate_tool_call_chunk(
+    accumulators: &mut BTreeMap<u32, ToolCallAccumulator>,
+    tool_call_chunk: &ChatCompletionMessageToolCallChunk,
+) {
+    let accumulator = accumulators
+        .entry(tool_call_chunk.index)
+        .or_insert_with(ToolCallAccumulator::default);
+
+    if let Some(id) = &tool_call_chunk.id {
+        accumulator.id = Some(id.clone());
+    }
+    if let Some(function) = &tool_call_chunk.function {
+        if let Some(name) = &function.name {
+            accumulator.name.push_str(name);
+        }
+        if let Some(arguments) = &function.arguments {
+            accumulator.arguments.push_str(arguments);
+        }
+    }
+}
+
+/// Runs every tool call concurrently on the blocking thread pool and returns
+/// the results in the original call order, so slow calls don't hold up
+/// independent ones and `tool_call_id` pairing is preserved for the
+/// follow-up request.
+async fn run_tool_calls(
+    tool_calls: Vec<ChatCompletionMessageToolCall>,
+    auto_approve: bool,
+) -> Result<Vec<(ChatCompletionMessageToolCall, ToolContent)>> {
+    // Approvals are gathered up front, sequentially, on this task — not
+    // inside the spawned blocking workers below. If two Execute-kind calls
+    // (e.g. two `Bash` calls) were each allowed to print their own banner
+    // and read stdin from a `spawn_blocking` thread, the prompts would
+    // interleave and both threads would race to read the same input line.
+    // Resolving every decision here, before anything concurrent starts,
+    // keeps approval prompts one at a time.
+    let mut approvals = Vec::with_capacity(tool_calls.len());
+    for tool_call in &tool_calls {
+        approvals.push(approve_tool_call(tool_call, auto_approve)?);
+    }
+
+    let len = tool_calls.len();
+    let mut pending = FuturesUnordered::new();
+    for (index, (tool_call, approved)) in tool_calls.into_iter().zip(approvals).enumerate() {
+        pending.push(tokio::task::spawn_blocking(move || {
+            let response = dispatch_tool_call(&tool_call, approved);
+            (index, tool_call, response)
+        }));
+    }
+
+    let mut results: Vec<Option<(ChatCompletionMessageToolCall, ToolContent)>> =
+        (0..len).map(|_| None).collect();
+    while let Some(joined) = pending.next().await {
+        let (index, tool_call, response) = joined?;
+        results[index] = Some((tool_call, response));
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Resolves whether a tool call is allowed to run: read-only tools and
+/// calls with malformed arguments (which `dispatch_tool_call` will reject
+/// on its own) always pass through; `Execute`-kind calls are confirmed via
+/// [`request_approval`] unless `auto_approve` is set.
+fn approve_tool_call(tool_call: &ChatCompletionMessageToolCall, auto_approve: bool) -> Result<bool> {
+    let name = tool_call.function.name.as_str();
+    if auto_approve || tool_kind(name) != ToolKind::Execute {
+        return Ok(true);
+    }
+
+    let Ok(args) = serde_json::from_str::<Value>(&tool_call.function.arguments) else {
+        return Ok(true);
+    };
+
+    request_approval(name, &args)
+}
+
+fn dispatch_tool_call(tool_call: &ChatCompletionMessageToolCall, approved: bool) -> ToolContent {
+    let name = tool_call.function.name.as_str();
+    eprintln!("Calling {name} function.");
+    let args: Value = match serde_json::from_str(&tool_call.function.arguments) {
+        Ok(args) => args,
+        Err(_) => {
+            let err_msg = format!("Tool call '{name}' is invalid: arguments must be valid JSON");
+            return ToolContent::Json(json!(err_msg));
+        }
+    };
+    eprintln!("{args:?}");
+
+    if tool_kind(name) == ToolKind::Execute && !approved {
+        let err_msg = format!("User declined to run the '{name}' tool call.");
+        return ToolContent::Json(json!(err_msg));
+    }
+
+    let result = match name {
+        "Read" => call_read_tool(&args),
+        "Write" => call_write_tool(&args),
+        "Bash" => call_bash_tool(&args),
+        _ => Err(anyhow!("Unknown tool: {name}")),
+    };
+    match result {
+        Ok(content) => content,
+        Err(err) => ToolContent::Json(json!(err.to_string())),
+    }
+}
+
+/// Describes the proposed side effect of an `Execute`-kind tool call and
+/// asks the user to confirm it on stdin. Returns whether the call should
+/// proceed.
+fn request_approval(name: &str, args: &Value) -> Result<bool> {
+    // Rendered via `{:?}` rather than `{}`: the values come straight from the
+    // model's tool-call JSON, and Debug-formatting a `&str` escapes control
+    // characters (e.g. ANSI sequences) instead of letting them reach the
+    // terminal, where they could rewrite what this prompt appears to show.
+    let description = match name {
+        "Write" => {
+            let file_path = args["file_path"].as_str().unwrap_or("<unknown>");
+            let content = args["content"].as_str().unwrap_or("");
+            format!("Write to {file_path:?}:\n{content:?}")
+        }
+        "Bash" => {
+            let command = args["command"].as_str().unwrap_or("<unknown>");
+            format!("Run command: {command:?}")
+        }
+        _ => name.to_string(),
+    };
+
+    eprintln!("--- Approval required ---\n{description}\n--------------------------");
+    eprint!("Proceed? [y/N] ");
+    std::io::stderr().flush()?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
 fn append_tool_responses(
     request: &mut CreateChatCompletionRequest,
-    function_responses: &[(&ChatCompletionMessageToolCall, Value)],
+    function_responses: &[(&ChatCompletionMessageToolCall, &ToolContent)],
 ) -> Result<()> {
     // Convert ChatCompletionMessageToolCall to ChatCompletionMessageToolCalls enum
     let tool_calls: Vec<ChatCompletionMessageToolCalls> = function_responses
@@ -122,41 +511,82 @@ fn append_tool_responses(
             .build()?
             .into();
 
-    let tool_messages: Vec<ChatCompletionRequestMessage> = function_responses
-        .iter()
-        .map(|(tool_call, response_content)| {
-            ChatCompletionRequestMessage::Tool(ChatCompletionRequestToolMessage {
-                content: response_content.to_string().into(),
+    let mut tool_messages = Vec::with_capacity(function_responses.len());
+    let mut image_followups = Vec::new();
+    for (tool_call, response_content) in function_responses {
+        let (content, image_followup) = tool_message_content(response_content)?;
+        tool_messages.push(ChatCompletionRequestMessage::Tool(
+            ChatCompletionRequestToolMessage {
+                content,
                 tool_call_id: tool_call.id.clone(),
-            })
-        })
-        .collect();
+            },
+        ));
+        image_followups.extend(image_followup);
+    }
 
     request.messages.push(assistant_messages);
     request.messages.extend(tool_messages);
+    // `tool`-role messages only carry string content per the OpenAI
+    // chat-completions schema; array/image content parts are documented
+    // only for `user` messages. So an image `Read` result can't ride along
+    // on the tool message itself — hand it to the model as a follow-up user
+    // message instead.
+    request.messages.extend(image_followups);
     Ok(())
 }
 
-fn call_read_tool<'tool_call>(
-    tool_call: &'tool_call ChatCompletionMessageToolCall,
-    args: &Value,
-    function_responses: &mut Vec<(&'tool_call ChatCompletionMessageToolCall, Value)>,
-) -> Result<()> {
+fn tool_message_content(
+    content: &ToolContent,
+) -> Result<(ChatCompletionRequestToolMessageContent, Option<ChatCompletionRequestMessage>)> {
+    match content {
+        ToolContent::Json(value) => Ok((value.to_string().into(), None)),
+        ToolContent::Image { mime_type, data } => {
+            let image_url = ImageUrlArgs::default()
+                .url(format!("data:{mime_type};base64,{data}"))
+                .build()?;
+            let image_part = ChatCompletionRequestUserMessageContentPart::ImageUrl(
+                ChatCompletionRequestMessageContentPartImageArgs::default()
+                    .image_url(image_url)
+                    .build()?,
+            );
+            let image_message: ChatCompletionRequestMessage =
+                ChatCompletionRequestUserMessageArgs::default()
+                    .content(vec![image_part])
+                    .build()?
+                    .into();
+
+            let tool_text = json!("Image file read successfully; see the attached image.");
+            Ok((tool_text.to_string().into(), Some(image_message)))
+        }
+    }
+}
+
+fn call_read_tool(args: &Value) -> Result<ToolContent> {
     let file_path = args["file_path"].as_str().context(format!(
         "Should have a `file_path` argument. Args were: {args:#?}"
     ))?;
+
+    let mime_type = mime_guess::from_path(file_path).first();
+    if let Some(mime_type) = mime_type.filter(|m| m.type_() == mime_guess::mime::IMAGE) {
+        let bytes = std::fs::read(file_path)?;
+        let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+        eprintln!("Read image file: {file_path} ({mime_type})");
+        return Ok(ToolContent::Image {
+            mime_type: mime_type.to_string(),
+            data,
+        });
+    }
+
     let file_contents = read_file_to_string(file_path)?;
     eprintln!("file contents: {file_contents}");
-    let file_contents = json!(&file_contents);
-    function_responses.push((tool_call, file_contents));
-    Ok(())
+    Ok(ToolContent::Json(json!(&file_contents)))
 }
 
-fn call_write_tool<'tool_call>(
-    tool_call: &'tool_call ChatCompletionMessageToolCall,
-    args: &Value,
-    function_responses: &mut Vec<(&'tool_call ChatCompletionMessageToolCall, Value)>,
-) -> Result<()> {
+/// # Side effects
+///
+/// Overwrites `file_path` on disk. Gated behind [`request_approval`] unless
+/// `--yes` is passed; the `serve` subcommand always approves it.
+fn call_write_tool(args: &Value) -> Result<ToolContent> {
     let file_path = args["file_path"]
         .as_str()
         .context("Should have a `file_path` argument.")?;
@@ -164,22 +594,19 @@ fn call_write_tool<'tool_call>(
         "Should have a `content` argument. Args were: {args:#?}"
     ))?;
     write_to_file(file_path, content)?;
-    let new_file_value = json!(content);
-    function_responses.push((tool_call, new_file_value));
-    Ok(())
+    Ok(ToolContent::Json(json!(content)))
 }
 
-fn call_bash_tool<'tool_call>(
-    tool_call: &'tool_call ChatCompletionMessageToolCall,
-    args: &Value,
-    function_responses: &mut Vec<(&'tool_call ChatCompletionMessageToolCall, Value)>,
-) -> Result<()> {
+/// # Side effects
+///
+/// Runs `command` in a shell. Gated behind [`request_approval`] unless
+/// `--yes` is passed; the `serve` subcommand always approves it.
+fn call_bash_tool(args: &Value) -> Result<ToolContent> {
     let command = args["command"].as_str().context(format!(
         "Should have a `command` argument. Args were: {args:#?}"
     ))?;
     let output = execute_bash_command(command)?;
-    function_responses.push((tool_call, json!(output)));
-    Ok(())
+    Ok(ToolContent::Json(json!(output)))
 }
 
 fn read_file_to_string(path: impl AsRef<Path>) -> Result<String> {
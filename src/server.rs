@@ -0,0 +1,209 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_openai::types::chat::{
+    ChatCompletionRequestMessage, ChatCompletionTools, CreateChatCompletionRequest,
+};
+use async_openai::{Client, config::OpenAIConfig};
+use futures::stream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::agent_turn;
+
+struct ServerState {
+    client: Client<OpenAIConfig>,
+    model: String,
+    max_tokens: u32,
+    tools: Vec<ChatCompletionTools>,
+    base_messages: Vec<ChatCompletionRequestMessage>,
+}
+
+/// Starts an HTTP server exposing `/v1/chat/completions` in OpenAI's
+/// request/response shape, backed by [`agent_turn`] — the same
+/// tool-calling loop the CLI and REPL use. The model, token limit, tool
+/// set and (if `--role` was given) the role's system prompt are fixed per
+/// server, but each request brings its own conversation messages.
+///
+/// There is no terminal to confirm an `Execute`-kind tool call against, so
+/// approval is always granted automatically here regardless of `--yes` —
+/// otherwise a `Write`/`Bash` call would block the handling task forever
+/// waiting on a stdin read nobody can answer.
+pub async fn serve(
+    addr: SocketAddr,
+    client: Client<OpenAIConfig>,
+    model: String,
+    max_tokens: u32,
+    tools: Vec<ChatCompletionTools>,
+    base_messages: Vec<ChatCompletionRequestMessage>,
+    auto_approve: bool,
+) -> Result<()> {
+    if !auto_approve {
+        eprintln!(
+            "WARNING: serve mode has no terminal to approve Write/Bash calls against; \
+             automatically approving every tool call as if --yes were passed."
+        );
+    }
+
+    let state = Arc::new(ServerState {
+        client,
+        model,
+        max_tokens,
+        tools,
+        base_messages,
+    });
+
+    let make_svc = make_service_fn(move |_conn| {
+        let state = Arc::clone(&state);
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let state = Arc::clone(&state);
+                async move { Ok::<_, Infallible>(handle_request(req, state).await) }
+            }))
+        }
+    });
+
+    eprintln!("Listening on http://{addr}");
+    Server::bind(&addr).serve(make_svc).await?;
+    Ok(())
+}
+
+async fn handle_request(req: Request<Body>, state: Arc<ServerState>) -> Response<Body> {
+    if req.method() != Method::POST || req.uri().path() != "/v1/chat/completions" {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .expect("building a static not-found response cannot fail");
+    }
+
+    match handle_chat_completions(req, state).await {
+        Ok(response) => response,
+        Err(err) => {
+            eprintln!("chat completion request failed: {err:?}");
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .expect("building an error response cannot fail")
+        }
+    }
+}
+
+async fn handle_chat_completions(
+    req: Request<Body>,
+    state: Arc<ServerState>,
+) -> Result<Response<Body>> {
+    let body_bytes = hyper::body::to_bytes(req.into_body()).await?;
+    let mut request: CreateChatCompletionRequest = serde_json::from_slice(&body_bytes)?;
+
+    request.model = state.model.clone();
+    request.max_completion_tokens = Some(state.max_tokens);
+    request.tools = Some(state.tools.clone());
+    request
+        .messages
+        .splice(0..0, state.base_messages.iter().cloned());
+    let stream = request.stream.unwrap_or(false);
+
+    if stream {
+        Ok(stream_response(state, request))
+    } else {
+        let content = agent_turn(&state.client, &mut request, true, |_delta| {}).await?;
+        json_response(&content, &state.model)
+    }
+}
+
+fn json_response(content: &str, model: &str) -> Result<Response<Body>> {
+    let body = json!({
+        "id": "chatcmpl-agent",
+        "object": "chat.completion",
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop",
+        }],
+    });
+    Ok(Response::builder()
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))?)
+}
+
+/// Runs `agent_turn` in the background and relays each assistant text delta
+/// it produces to the HTTP client as an SSE chunk as soon as it arrives,
+/// rather than buffering the full reply and sending it as a single chunk.
+/// Emits an initial `delta.role` chunk and, on success, a terminal
+/// `finish_reason: "stop"` chunk so strict OpenAI-compatible clients can
+/// tell a normal completion from a truncated stream; on failure it sends an
+/// error chunk instead of silently cutting the stream short.
+fn stream_response(state: Arc<ServerState>, mut request: CreateChatCompletionRequest) -> Response<Body> {
+    let (tx, rx) = mpsc::unbounded_channel::<String>();
+    let model = state.model.clone();
+
+    tokio::spawn(async move {
+        let role_chunk = json!({
+            "id": "chatcmpl-agent",
+            "object": "chat.completion.chunk",
+            "model": model,
+            "choices": [{
+                "index": 0,
+                "delta": { "role": "assistant" },
+                "finish_reason": null,
+            }],
+        });
+        let _ = tx.send(format!("data: {role_chunk}\n\n"));
+
+        let delta_tx = tx.clone();
+        let delta_model = model.clone();
+        let on_delta = move |delta: &str| {
+            let chunk = json!({
+                "id": "chatcmpl-agent",
+                "object": "chat.completion.chunk",
+                "model": delta_model,
+                "choices": [{
+                    "index": 0,
+                    "delta": { "content": delta },
+                    "finish_reason": null,
+                }],
+            });
+            let _ = delta_tx.send(format!("data: {chunk}\n\n"));
+        };
+
+        match agent_turn(&state.client, &mut request, true, on_delta).await {
+            Ok(_) => {
+                let final_chunk = json!({
+                    "id": "chatcmpl-agent",
+                    "object": "chat.completion.chunk",
+                    "model": model,
+                    "choices": [{
+                        "index": 0,
+                        "delta": {},
+                        "finish_reason": "stop",
+                    }],
+                });
+                let _ = tx.send(format!("data: {final_chunk}\n\n"));
+            }
+            Err(err) => {
+                eprintln!("streamed chat completion failed: {err:?}");
+                let error_chunk = json!({
+                    "error": { "message": err.to_string(), "type": "server_error" },
+                });
+                let _ = tx.send(format!("data: {error_chunk}\n\n"));
+            }
+        }
+        let _ = tx.send("data: [DONE]\n\n".to_string());
+    });
+
+    let body_stream = stream::unfold(rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|chunk| (Ok::<_, Infallible>(chunk), rx))
+    });
+
+    Response::builder()
+        .header("content-type", "text/event-stream")
+        .body(Body::wrap_stream(body_stream))
+        .expect("building a streaming response cannot fail")
+}